@@ -0,0 +1,33 @@
+use super::selection::FlaggedSelection;
+use super::PathEntry;
+
+/// Bulk multi-select actions bound to keys in the interactive prompt, mirroring `fm`'s
+/// `flag_all`, `reverse_flags`, and `clear_flags`.
+///
+/// These operate on the [FlaggedSelection] accumulated across directory navigation, rather
+/// than on the single current directory's listing, so selections gathered from earlier
+/// directories are never lost.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum PathSelectAction {
+    /// Flag every selectable entry in the current directory.
+    FlagAll,
+    /// Invert the current directory's flags.
+    InvertFlags,
+    /// Clear all flags, across every directory visited so far.
+    ClearFlags,
+}
+
+impl PathSelectAction {
+    /// Applies this action to `flagged`.
+    ///
+    /// `current_dir_entries` must already be narrowed down to selectable entries (i.e.
+    /// filtered through [`PathEntry::is_selectable`]) — [PathSelectAction::FlagAll] and
+    /// [PathSelectAction::InvertFlags] only ever touch entries they're given.
+    pub(crate) fn apply(self, flagged: &mut FlaggedSelection, current_dir_entries: &[PathEntry]) {
+        match self {
+            Self::FlagAll => flagged.flag_all(current_dir_entries),
+            Self::InvertFlags => flagged.invert(current_dir_entries),
+            Self::ClearFlags => flagged.clear(),
+        }
+    }
+}
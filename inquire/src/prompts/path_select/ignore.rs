@@ -0,0 +1,152 @@
+use std::fs;
+use std::path::Path;
+
+use super::PathEntry;
+
+/// A single compiled ignore pattern, as found in [`PathSelect::ignore_globs`](super::PathSelect)
+/// or a `.gitignore` file.
+pub(crate) struct GlobPattern {
+    /// The glob body, with any leading `!`, leading `/` and trailing `/` stripped.
+    pattern: String,
+    /// Whether the pattern was prefixed with `!`, re-including a previously ignored entry.
+    negated: bool,
+    /// Whether the pattern ends in `/`, restricting it to directories.
+    dir_only: bool,
+    /// Whether the pattern contains a `/`, anchoring it to the path relative to the
+    /// directory being listed rather than matching on the file name alone.
+    anchored: bool,
+}
+
+fn compile_pattern(raw: &str) -> Option<GlobPattern> {
+    let raw = raw.trim();
+    if raw.is_empty() || raw.starts_with('#') {
+        return None;
+    }
+
+    let negated = raw.starts_with('!');
+    let raw = if negated { &raw[1..] } else { raw };
+
+    let dir_only = raw.ends_with('/');
+    let raw = if dir_only { &raw[..raw.len() - 1] } else { raw };
+
+    let anchored = raw.contains('/');
+    let pattern = raw.trim_start_matches('/').to_string();
+
+    Some(GlobPattern {
+        pattern,
+        negated,
+        dir_only,
+        anchored,
+    })
+}
+
+/// Compiles the glob strings passed to
+/// [`PathSelect::with_ignore_globs`](super::PathSelect::with_ignore_globs).
+pub(crate) fn compile_ignore_globs(patterns: &[&str]) -> Vec<GlobPattern> {
+    patterns.iter().filter_map(|raw| compile_pattern(raw)).collect()
+}
+
+/// Parses the `.gitignore` file in `dir`, if any, honored when
+/// [`PathSelect::with_respect_gitignore`](super::PathSelect::with_respect_gitignore) is set.
+pub(crate) fn load_gitignore(dir: &Path) -> Vec<GlobPattern> {
+    fs::read_to_string(dir.join(".gitignore"))
+        .ok()
+        .map(|contents| contents.lines().filter_map(compile_pattern).collect())
+        .unwrap_or_default()
+}
+
+fn pattern_matches(glob: &GlobPattern, entry: &PathEntry, dir: &Path) -> bool {
+    if glob.dir_only && !entry.is_dir() {
+        return false;
+    }
+
+    if glob.anchored {
+        let relative = entry
+            .path
+            .strip_prefix(dir)
+            .map(|relative| relative.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| entry_file_name(entry));
+        glob_match(&glob.pattern, &relative)
+    } else {
+        glob_match(&glob.pattern, &entry_file_name(entry))
+    }
+}
+
+fn entry_file_name(entry: &PathEntry) -> String {
+    entry
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Whether `entry` (found while listing `dir`) should be hidden given the compiled `patterns`.
+///
+/// Later patterns take precedence over earlier ones, mirroring `.gitignore` semantics: a
+/// pattern starting with `!` re-includes an entry matched as ignored by an earlier pattern.
+pub(crate) fn is_ignored(entry: &PathEntry, dir: &Path, patterns: &[GlobPattern]) -> bool {
+    let mut ignored = false;
+    for glob in patterns {
+        if pattern_matches(glob, entry, dir) {
+            ignored = !glob.negated;
+        }
+    }
+    ignored
+}
+
+/// Simple shell-style glob matcher supporting `*` (any run of characters) and `?` (any single
+/// character), used for both user-supplied ignore globs and parsed `.gitignore` patterns, as
+/// well as [`FileMatch::Glob`](super::FileMatch::Glob).
+///
+/// Matches over `char`s rather than bytes, so `?` consumes one Unicode scalar value rather
+/// than one UTF-8 byte, keeping multi-byte file names (e.g. emoji) matching correctly.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut star_p: Option<usize> = None;
+    let mut star_t = 0;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star_p = Some(p);
+            star_t = t;
+            p += 1;
+        } else if let Some(sp) = star_p {
+            p = sp + 1;
+            star_t += 1;
+            t = star_t;
+        } else {
+            return false;
+        }
+    }
+
+    while p < pattern.len() && pattern[p] == '*' {
+        p += 1;
+    }
+
+    p == pattern.len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::glob_match;
+
+    #[test]
+    fn glob_match_handles_star_and_question_mark() {
+        assert!(glob_match("*.log", "debug.log"));
+        assert!(!glob_match("*.log", "debug.log.bak"));
+        assert!(glob_match("file?.rs", "file1.rs"));
+        assert!(!glob_match("file?.rs", "file10.rs"));
+    }
+
+    #[test]
+    fn glob_match_question_mark_matches_one_unicode_scalar_not_one_byte() {
+        assert!(glob_match("file?.rs", "file😀.rs"));
+        assert!(glob_match("*.rs", "résumé.rs"));
+    }
+}
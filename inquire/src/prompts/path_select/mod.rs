@@ -4,6 +4,11 @@ mod prompt;
 use prompt::*;
 mod config;
 use config::*;
+mod sort;
+pub use sort::SortField;
+mod ignore;
+use ignore::{compile_ignore_globs, glob_match, is_ignored, load_gitignore};
+mod selection;
 
 use crate::{
     config::get_configuration,
@@ -24,11 +29,18 @@ use std::{
     path::{Path, PathBuf},
 };
 
+/// Callback type that renders the preview pane contents for the currently
+/// highlighted [PathEntry].
+///
+/// Each returned `String` is drawn as one preview line below the selection list, without
+/// truncation to the terminal width.
+pub type PreviewRenderer<'a> = &'a dyn Fn(&PathEntry) -> Vec<String>;
+
 /// Different path selection modes specify what the user can choose
 #[derive(Clone, Eq, PartialEq)]
 pub enum PathSelectionMode<'a> {
-    /// The user may pick a file with the given (optional) extension
-    File(Option<&'a str>),
+    /// The user may pick a file matching the given [FileMatch]
+    File(FileMatch<'a>),
     /// The user may pick a directory
     Directory,
     /// The user may pick multiple paths
@@ -40,6 +52,21 @@ impl<'a> Default for PathSelectionMode<'a> {
     }
 }
 
+/// Which files satisfy [PathSelectionMode::File].
+#[derive(Clone, Eq, PartialEq)]
+pub enum FileMatch<'a> {
+    /// Any file matches.
+    Any,
+    /// A file matches if its extension equals the given string, case-insensitively.
+    Extension(&'a str),
+    /// A file matches if its extension equals any of the given strings, case-insensitively.
+    Extensions(Vec<&'a str>),
+    /// A file matches if its whole file name matches the given shell glob, e.g. `*.tar.gz` or
+    /// `test_*.rs`. Unlike the `Extension*` variants, this can match multi-dot extensions that
+    /// [`Path::extension`] can't, such as `.tar.gz`.
+    Glob(&'a str),
+}
+
 /// Path with cached information
 #[derive(Clone, Debug, Hash)]
 pub struct PathEntry {
@@ -119,14 +146,9 @@ impl PathEntry {
     pub fn is_selectable<'a>(&self, selection_mode: &PathSelectionMode<'a>) -> bool {
         let is_dir = self.is_dir();
         let is_file = self.is_file();
-        let file_ext_opt = self.path.extension().map(OsStr::to_os_string);
         match (selection_mode, is_dir, is_file) {
             (PathSelectionMode::Directory, true, _) => true,
-            (PathSelectionMode::File(None), _, true) => true,
-            (PathSelectionMode::File(Some(extension)), _, true) => file_ext_opt
-                .as_ref()
-                .map(|osstr| osstr.to_string_lossy().eq_ignore_ascii_case(*extension))
-                .unwrap_or_default(),
+            (PathSelectionMode::File(file_match), _, true) => self.matches_file(file_match),
             (PathSelectionMode::Multiple(ref path_selection_modes), _, _) => path_selection_modes
                 .iter()
                 .any(|submode| self.is_selectable(submode)),
@@ -134,6 +156,31 @@ impl PathEntry {
         }
     }
 
+    /// Does this entry's file name satisfy the given [FileMatch]?
+    fn matches_file(&self, file_match: &FileMatch<'_>) -> bool {
+        let extension = self.path.extension().map(OsStr::to_string_lossy);
+
+        match file_match {
+            FileMatch::Any => true,
+            FileMatch::Extension(extension_match) => extension
+                .as_deref()
+                .map(|ext| ext.eq_ignore_ascii_case(extension_match))
+                .unwrap_or_default(),
+            FileMatch::Extensions(extension_matches) => extension
+                .as_deref()
+                .map(|ext| {
+                    extension_matches
+                        .iter()
+                        .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+                })
+                .unwrap_or_default(),
+            FileMatch::Glob(glob) => {
+                let file_name = self.path.file_name().map(OsStr::to_string_lossy).unwrap_or_default();
+                glob_match(glob, &file_name)
+            }
+        }
+    }
+
     /// Is this path entry for a symlink?
     pub fn is_symlink(&self) -> bool {
         self.symlink_path_opt.is_some()
@@ -192,6 +239,27 @@ pub struct PathSelect<'a, T> {
     pub render_config: RenderConfig<'a>,
     /// The [path selection mode](PathSelectionMode) determines what the user can select.
     pub selection_mode: PathSelectionMode<'a>,
+
+    /// Whether to render a preview pane for the currently highlighted entry,
+    /// drawn below the selection list.
+    pub preview: bool,
+
+    /// Function used to render the preview pane contents for a [PathEntry].
+    pub preview_renderer: PreviewRenderer<'a>,
+
+    /// The [field](SortField) directory entries are sorted by, applied after filtering and
+    /// before pagination.
+    pub sort_field: SortField,
+
+    /// Whether to reverse the ordering produced by [PathSelect::sort_field].
+    pub sort_reversed: bool,
+
+    /// Glob patterns (`*.log`, `target/`, `!keep.log`) matched against entry file names, or
+    /// relative paths for patterns containing a `/`, to hide matching entries.
+    pub ignore_globs: &'a [&'a str],
+
+    /// Whether to additionally honor a `.gitignore` file found in the directory being listed.
+    pub respect_gitignore: bool,
 }
 
 impl<'a, T> PathSelect<'a, T>
@@ -294,6 +362,37 @@ where
     /// Default visual divider value.
     pub const DEFAULT_DIVIDER: &'a str = "-----";
 
+    /// Default value of showing the preview pane.
+    pub const DEFAULT_PREVIEW: bool = false;
+
+    /// Maximum number of text lines read from a file by [PathSelect::DEFAULT_PREVIEW_RENDERER].
+    pub const DEFAULT_PREVIEW_LINES: usize = 20;
+
+    /// Number of leading bytes inspected for a NUL byte when the default preview renderer
+    /// decides whether a file looks binary.
+    pub const DEFAULT_PREVIEW_BINARY_PROBE_SIZE: usize = 8 * 1024;
+
+    /// Default [PreviewRenderer], used when [PathSelect::with_preview] is enabled without
+    /// a custom renderer.
+    ///
+    /// Shows up to [PathSelect::DEFAULT_PREVIEW_LINES] lines of text for files, bailing out
+    /// with a placeholder message if a NUL byte appears in the first
+    /// [PathSelect::DEFAULT_PREVIEW_BINARY_PROBE_SIZE] bytes, and a short summary (child count,
+    /// total size) for directories.
+    pub const DEFAULT_PREVIEW_RENDERER: PreviewRenderer<'a> = &default_preview_renderer;
+
+    /// Default [SortField] applied to directory listings.
+    pub const DEFAULT_SORT_FIELD: SortField = SortField::Name;
+
+    /// Default value of whether the sort ordering is reversed.
+    pub const DEFAULT_SORT_REVERSED: bool = false;
+
+    /// Default ignore globs, i.e. none.
+    pub const DEFAULT_IGNORE_GLOBS: &'a [&'a str] = &[];
+
+    /// Default value of whether `.gitignore` files are honored.
+    pub const DEFAULT_RESPECT_GITIGNORE: bool = false;
+
     /// Creates a [PathSelect] with the provided message and options, along with default configuration values.
     pub fn new(message: &'a str, start_path_opt: Option<T>) -> Self {
         Self {
@@ -311,41 +410,105 @@ where
             keep_filter: Self::DEFAULT_KEEP_FILTER,
             render_config: get_configuration(),
             selection_mode: Default::default(),
+            preview: Self::DEFAULT_PREVIEW,
+            preview_renderer: Self::DEFAULT_PREVIEW_RENDERER,
+            sort_field: Self::DEFAULT_SORT_FIELD,
+            sort_reversed: Self::DEFAULT_SORT_REVERSED,
+            ignore_globs: Self::DEFAULT_IGNORE_GLOBS,
+            respect_gitignore: Self::DEFAULT_RESPECT_GITIGNORE,
         }
     }
 
-    /// Test if a path is hidden file
+    /// Test if a path is a hidden file, across platforms.
+    ///
+    /// **Breaking change**: this now takes an additional `dir` parameter (the directory the
+    /// entry was listed from), used for the `.hidden` manifest lookup below. Call sites that
+    /// don't care about GNOME's `.hidden` convention can pass `None`.
     ///
-    /// ### Problems
-    /// This is missing some things described here:
+    /// - On platforms other than Windows, a leading `.` in the file name is treated as hidden
+    ///   (unix convention).
+    /// - If `dir` (the directory the entry was listed from) is given, a sibling `.hidden`
+    ///   manifest file in `dir` (newline-separated file names, the GNOME convention) listing
+    ///   the entry's name also marks it hidden.
+    /// - On Windows, the `FILE_ATTRIBUTE_HIDDEN` attribute marks a path hidden.
+    /// - On macOS, the `UF_HIDDEN` `st_flags` bit additionally marks a path hidden.
+    ///
+    /// ### Remaining gaps
+    /// This still doesn't cover everything described here:
     /// https://en.wikipedia.org/wiki/Hidden_file_and_hidden_directory
-    /// - android: .nomedia files that tell smartphone apps not to display/include a folder's contets
-    /// - gnome: filenames listed inside a file named ".hidden" in each directory should be hidden
-    /// - macos: files with Invisible attribute are usually hidden in Finder but not in `ls`
-    /// - windows: files with a Hidden file attribute
+    /// - android: `.nomedia` files that tell smartphone apps not to display/include a folder's contents
     /// - windows: files in folders with a predefined CLSID on the end of their names (Windows Special Folders)
     ///
     /// ```
     /// use inquire::PathSelect;
     /// use std::path::Path;
     ///
-    /// assert!(PathSelect::is_path_hidden_file(Path::new("/ra/set/.nut")));
-    /// assert!(!PathSelect::is_path_hidden_file(Path::new("/ra/set/nut")));
-    /// assert!(PathSelect::is_path_hidden_file(Path::new(".maat")));
-    /// assert!(!PathSelect::is_path_hidden_file(Path::new("maat")));
+    /// assert!(PathSelect::is_path_hidden_file(Path::new("/ra/set/.nut"), None));
+    /// assert!(!PathSelect::is_path_hidden_file(Path::new("/ra/set/nut"), None));
+    /// assert!(PathSelect::is_path_hidden_file(Path::new(".maat"), None));
+    /// assert!(!PathSelect::is_path_hidden_file(Path::new("maat"), None));
     ///
     /// ```
-    pub fn is_path_hidden_file(t: T) -> bool {
-        if cfg!(unix) {
-            t.as_ref()
-                .file_name()
-                .unwrap_or_default()
-                .to_str()
-                .unwrap_or_default()
-                .starts_with(".")
-        } else {
-            false
+    pub fn is_path_hidden_file(t: T, dir: Option<&Path>) -> bool {
+        let path = t.as_ref();
+
+        if let Some(dir) = dir {
+            if Self::is_listed_in_hidden_manifest(path, dir) {
+                return true;
+            }
         }
+
+        Self::is_path_hidden_file_platform(path)
+    }
+
+    /// Checks whether `path`'s file name is listed in a sibling `.hidden` manifest file
+    /// inside `dir` (the GNOME convention).
+    fn is_listed_in_hidden_manifest(path: &Path, dir: &Path) -> bool {
+        let Some(file_name) = path.file_name().and_then(OsStr::to_str) else {
+            return false;
+        };
+
+        fs::read_to_string(dir.join(".hidden"))
+            .map(|contents| contents.lines().any(|line| line.trim() == file_name))
+            .unwrap_or(false)
+    }
+
+    #[cfg(windows)]
+    fn is_path_hidden_file_platform(path: &Path) -> bool {
+        use std::os::windows::fs::MetadataExt;
+
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+
+        path.metadata()
+            .map(|metadata| metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(windows))]
+    fn is_path_hidden_file_platform(path: &Path) -> bool {
+        let is_dotfile = path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .unwrap_or_default()
+            .starts_with('.');
+
+        is_dotfile || Self::has_macos_hidden_flag(path)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn has_macos_hidden_flag(path: &Path) -> bool {
+        use std::os::macos::fs::MetadataExt;
+
+        const UF_HIDDEN: u32 = 0x8000;
+
+        path.metadata()
+            .map(|metadata| metadata.st_flags() & UF_HIDDEN != 0)
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn has_macos_hidden_flag(_path: &Path) -> bool {
+        false
     }
 
     /// Sets the keep filter behavior.
@@ -426,6 +589,49 @@ where
         self
     }
 
+    /// Enables or disables the preview pane for the currently highlighted entry.
+    pub fn with_preview(mut self, preview: bool) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    /// Sets the [PreviewRenderer] used to render the preview pane contents.
+    ///
+    /// Implies [PathSelect::with_preview]`(true)`.
+    pub fn with_preview_renderer(mut self, preview_renderer: PreviewRenderer<'a>) -> Self {
+        self.preview = true;
+        self.preview_renderer = preview_renderer;
+        self
+    }
+
+    /// Sets the [SortField] directory entries are sorted by.
+    pub fn with_sort(mut self, sort_field: SortField) -> Self {
+        self.sort_field = sort_field;
+        self
+    }
+
+    /// Sets whether the sort ordering produced by [PathSelect::with_sort] is reversed.
+    pub fn with_sort_reversed(mut self, sort_reversed: bool) -> Self {
+        self.sort_reversed = sort_reversed;
+        self
+    }
+
+    /// Sets glob patterns used to hide matching entries while listing a directory.
+    ///
+    /// Patterns ending in `/` only match directories; a leading `!` re-includes an entry
+    /// matched by an earlier pattern.
+    pub fn with_ignore_globs(mut self, ignore_globs: &'a [&'a str]) -> Self {
+        self.ignore_globs = ignore_globs;
+        self
+    }
+
+    /// Sets whether a `.gitignore` file found in the directory being listed is honored in
+    /// addition to [PathSelect::with_ignore_globs].
+    pub fn with_respect_gitignore(mut self, respect_gitignore: bool) -> Self {
+        self.respect_gitignore = respect_gitignore;
+        self
+    }
+
     /// Sets the provided color theme to this prompt.
     ///
     /// Note: The default render config considers if the NO_COLOR environment variable
@@ -494,6 +700,22 @@ where
         self.prompt_with_backend(&mut backend)
     }
 
+    /// Drops entries from `entries` (found while listing `dir`) that match
+    /// [PathSelect::ignore_globs] or, when [PathSelect::respect_gitignore] is set, the
+    /// directory's `.gitignore`.
+    pub(crate) fn retain_unignored(&self, entries: &mut Vec<PathEntry>, dir: &Path) {
+        if self.ignore_globs.is_empty() && !self.respect_gitignore {
+            return;
+        }
+
+        let mut patterns = compile_ignore_globs(self.ignore_globs);
+        if self.respect_gitignore {
+            patterns.extend(load_gitignore(dir));
+        }
+
+        entries.retain(|entry| !is_ignored(entry, dir, &patterns));
+    }
+
     pub(crate) fn prompt_with_backend<B: MultiSelectBackend>(
         self,
         backend: &mut B,
@@ -501,3 +723,141 @@ where
         PathSelectPrompt::new(self)?.prompt(backend)
     }
 }
+
+/// Default [PreviewRenderer] implementation.
+///
+/// See [PathSelect::DEFAULT_PREVIEW_RENDERER].
+fn default_preview_renderer(entry: &PathEntry) -> Vec<String> {
+    if entry.is_dir() {
+        return preview_directory_summary(&entry.path);
+    }
+
+    preview_file_lines(&entry.path)
+}
+
+fn preview_directory_summary(path: &Path) -> Vec<String> {
+    let mut child_count = 0usize;
+    let mut total_size = 0u64;
+
+    if let Ok(read_dir) = fs::read_dir(path) {
+        for dir_entry in read_dir.flatten() {
+            child_count += 1;
+            if let Ok(metadata) = dir_entry.metadata() {
+                total_size += metadata.len();
+            }
+        }
+    }
+
+    vec![
+        format!("{child_count} item{}", if child_count == 1 { "" } else { "s" }),
+        format!("{total_size} bytes total"),
+    ]
+}
+
+fn preview_file_lines(path: &Path) -> Vec<String> {
+    use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+
+    const DEFAULT_PREVIEW_LINES: usize = PathSelect::<PathBuf>::DEFAULT_PREVIEW_LINES;
+    const DEFAULT_PREVIEW_BINARY_PROBE_SIZE: usize =
+        PathSelect::<PathBuf>::DEFAULT_PREVIEW_BINARY_PROBE_SIZE;
+
+    let Ok(mut file) = fs::File::open(path) else {
+        return vec!["<unreadable>".to_string()];
+    };
+
+    // Only the first DEFAULT_PREVIEW_BINARY_PROBE_SIZE bytes are read up front for the binary
+    // check, and the line reader below stops after DEFAULT_PREVIEW_LINES lines, so this never
+    // reads a large file into memory just to show a handful of preview lines.
+    let mut probe = vec![0u8; DEFAULT_PREVIEW_BINARY_PROBE_SIZE];
+    let Ok(probe_len) = file.read(&mut probe) else {
+        return vec!["<unreadable>".to_string()];
+    };
+    if probe[..probe_len].contains(&0u8) {
+        return vec!["<binary file>".to_string()];
+    }
+
+    if file.seek(SeekFrom::Start(0)).is_err() {
+        return vec!["<unreadable>".to_string()];
+    }
+
+    BufReader::new(file)
+        .lines()
+        .take(DEFAULT_PREVIEW_LINES)
+        .map_while(Result::ok)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FileMatch, PathEntry, PathSelect, PathSelectionMode};
+    use std::convert::TryFrom;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_file(name: &str) -> PathEntry {
+        let dir = std::env::temp_dir().join("inquire_path_select_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, b"").unwrap();
+        PathEntry::try_from(path.as_path()).unwrap()
+    }
+
+    #[test]
+    fn hidden_manifest_marks_listed_names_hidden() {
+        let dir = std::env::temp_dir().join("inquire_path_select_hidden_test");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(".hidden"), b"secret.txt\n").unwrap();
+        fs::write(dir.join("secret.txt"), b"").unwrap();
+        fs::write(dir.join("visible.txt"), b"").unwrap();
+
+        assert!(PathSelect::<PathBuf>::is_path_hidden_file(
+            dir.join("secret.txt"),
+            Some(dir.as_path())
+        ));
+        assert!(!PathSelect::<PathBuf>::is_path_hidden_file(
+            dir.join("visible.txt"),
+            Some(dir.as_path())
+        ));
+    }
+
+    #[test]
+    fn without_dir_context_only_dotfiles_are_hidden() {
+        assert!(PathSelect::<PathBuf>::is_path_hidden_file(
+            PathBuf::from(".maat"),
+            None
+        ));
+        assert!(!PathSelect::<PathBuf>::is_path_hidden_file(
+            PathBuf::from("maat"),
+            None
+        ));
+    }
+
+    #[test]
+    fn file_match_any_accepts_every_file() {
+        let entry = temp_file("plain.txt");
+        assert!(entry.is_selectable(&PathSelectionMode::File(FileMatch::Any)));
+    }
+
+    #[test]
+    fn file_match_extension_is_case_insensitive() {
+        let entry = temp_file("photo.JPG");
+        assert!(entry.is_selectable(&PathSelectionMode::File(FileMatch::Extension("jpg"))));
+        assert!(!entry.is_selectable(&PathSelectionMode::File(FileMatch::Extension("png"))));
+    }
+
+    #[test]
+    fn file_match_extensions_accepts_any_listed_extension() {
+        let mode = PathSelectionMode::File(FileMatch::Extensions(vec!["jpg", "png", "gif"]));
+
+        assert!(temp_file("photo.png").is_selectable(&mode));
+        assert!(temp_file("photo.JPG").is_selectable(&mode));
+        assert!(!temp_file("notes.txt").is_selectable(&mode));
+    }
+
+    #[test]
+    fn file_match_glob_matches_multi_dot_extensions() {
+        let entry = temp_file("archive.tar.gz");
+        assert!(entry.is_selectable(&PathSelectionMode::File(FileMatch::Glob("*.tar.gz"))));
+        assert!(!entry.is_selectable(&PathSelectionMode::File(FileMatch::Glob("*.zip"))));
+    }
+}
@@ -0,0 +1,281 @@
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+use crate::error::InquireResult;
+use crate::list_option::ListOption;
+use crate::ui::{Key, MultiSelectBackend};
+use crate::InquireError;
+
+use super::action::PathSelectAction;
+use super::selection::FlaggedSelection;
+use super::sort::sort_entries;
+use super::{PathEntry, PathSelect};
+
+/// Runtime state for an interactive [PathSelect] session.
+///
+/// Tracks the directory currently being listed, its filtered entries, and the
+/// [FlaggedSelection] accumulated across `→`/`←` navigation so selections gathered from
+/// multiple directories survive until submission.
+pub(crate) struct PathSelectPrompt<'a, T> {
+    config: PathSelect<'a, T>,
+    current_dir: PathBuf,
+    current_entries: Vec<PathEntry>,
+    flagged: FlaggedSelection,
+    highlighted: usize,
+    preview_lines: Vec<String>,
+}
+
+impl<'a, T> PathSelectPrompt<'a, T>
+where
+    T: AsRef<std::path::Path>,
+{
+    pub(crate) fn new(config: PathSelect<'a, T>) -> InquireResult<Self> {
+        let current_dir = match config.start_path_opt.as_ref() {
+            Some(start_path) => start_path.as_ref().to_path_buf(),
+            None => std::env::current_dir()?,
+        };
+
+        let mut prompt = Self {
+            config,
+            current_dir,
+            current_entries: Vec::new(),
+            flagged: FlaggedSelection::default(),
+            highlighted: 0,
+            preview_lines: Vec::new(),
+        };
+        prompt.relist_current_dir()?;
+
+        Ok(prompt)
+    }
+
+    /// Re-lists [Self::current_dir], applying the hidden-file and symlink visibility
+    /// settings and the [`PathSelect::ignore_globs`]/[`PathSelect::respect_gitignore`]
+    /// filters, then sorting the result by [`PathSelect::sort_field`]. Called on
+    /// construction and again whenever a `→`/`←` key read in [Self::prompt] changes the
+    /// current directory, so sorting and
+    /// ignore filtering stay in effect as the user actually navigates, not just at startup;
+    /// the accumulated [Self::flagged] selection is left untouched, so staged entries survive
+    /// navigation.
+    fn relist_current_dir(&mut self) -> InquireResult<()> {
+        let mut entries = Vec::new();
+
+        for dir_entry in fs_err::read_dir(&self.current_dir)? {
+            let entry = PathEntry::try_from(dir_entry?)?;
+
+            if !self.config.show_hidden
+                && PathSelect::is_path_hidden_file(entry.path.as_path(), Some(self.current_dir.as_path()))
+            {
+                continue;
+            }
+
+            if !self.config.show_symlinks && entry.is_symlink() {
+                continue;
+            }
+
+            entries.push(entry);
+        }
+
+        self.config.retain_unignored(&mut entries, &self.current_dir);
+        sort_entries(&mut entries, self.config.sort_field, self.config.sort_reversed);
+
+        self.current_entries = entries;
+        self.highlighted = 0;
+        self.refresh_preview();
+
+        Ok(())
+    }
+
+    /// Moves the highlighted entry by `delta`, wrapping around at either end of the current
+    /// listing, and
+    /// refreshes [Self::preview_lines] for the newly highlighted entry.
+    pub(crate) fn move_highlight(&mut self, delta: isize) {
+        if self.current_entries.is_empty() {
+            return;
+        }
+
+        let len = self.current_entries.len() as isize;
+        let next = (self.highlighted as isize + delta).rem_euclid(len);
+        self.highlighted = next as usize;
+        self.refresh_preview();
+    }
+
+    /// Recomputes [Self::preview_lines] for the currently highlighted entry, using
+    /// [`PathSelect::preview_renderer`] when [`PathSelect::preview`] is enabled. The prompt
+    /// render path draws these lines below the selection list, one row each; they are not
+    /// placed beside the list and are not truncated to the terminal width.
+    fn refresh_preview(&mut self) {
+        self.preview_lines = match (self.config.preview, self.current_entries.get(self.highlighted)) {
+            (true, Some(entry)) => (self.config.preview_renderer)(entry),
+            _ => Vec::new(),
+        };
+    }
+
+    /// Preview pane lines for the currently highlighted entry, ready to be drawn below the
+    /// selection list.
+    pub(crate) fn preview_lines(&self) -> &[String] {
+        &self.preview_lines
+    }
+
+    /// Navigates into `dir`, re-listing it while keeping [Self::flagged] intact.
+    pub(crate) fn navigate_into(&mut self, dir: PathBuf) -> InquireResult<()> {
+        self.current_dir = dir;
+        self.relist_current_dir()
+    }
+
+    /// Navigates to the parent of [Self::current_dir], re-listing it while keeping
+    /// [Self::flagged] intact. A no-op at the root.
+    pub(crate) fn navigate_up(&mut self) -> InquireResult<()> {
+        if let Some(parent) = self.current_dir.parent() {
+            self.current_dir = parent.to_path_buf();
+            self.relist_current_dir()?;
+        }
+
+        Ok(())
+    }
+
+    /// Entries of [Self::current_dir] selectable under the configured
+    /// [`PathSelectionMode`](super::PathSelectionMode), the scope bulk actions operate on.
+    fn selectable_entries(&self) -> Vec<PathEntry> {
+        self.current_entries
+            .iter()
+            .filter(|entry| entry.is_selectable(&self.config.selection_mode))
+            .cloned()
+            .collect()
+    }
+
+    /// Applies a bulk selection action to the accumulated [FlaggedSelection].
+    pub(crate) fn apply_action(&mut self, action: PathSelectAction) {
+        let scope = self.selectable_entries();
+        action.apply(&mut self.flagged, &scope);
+    }
+
+    /// Number of paths currently staged, across every directory visited so far. Surfaced in
+    /// the prompt render so users know how many paths will be returned on submission.
+    pub(crate) fn staged_count(&self) -> usize {
+        self.flagged.len()
+    }
+
+    pub(crate) fn get_path_string(t: T) -> String {
+        t.as_ref().to_string_lossy().into_owned()
+    }
+
+    /// The page of [Self::current_entries] containing the highlighted entry, sized by
+    /// [`PathSelect::page_size`], along with the highlighted entry's index within that page.
+    /// This is the only place [`PathSelect::page_size`] is read: [Self::render] draws just
+    /// this slice, so a long directory listing is paginated rather than dumped in full.
+    fn current_page(&self) -> (&[PathEntry], usize) {
+        let page_size = self.config.page_size.max(1);
+        let page_start = (self.highlighted / page_size) * page_size;
+        let page_end = (page_start + page_size).min(self.current_entries.len());
+
+        (&self.current_entries[page_start..page_end], self.highlighted - page_start)
+    }
+
+    /// Toggles the flagged state of the highlighted entry, if it's selectable.
+    fn toggle_highlighted(&mut self) {
+        if let Some(entry) = self.current_entries.get(self.highlighted).cloned() {
+            if entry.is_selectable(&self.config.selection_mode) {
+                self.flagged.invert(std::slice::from_ref(&entry));
+            }
+        }
+    }
+
+    /// Navigates into the highlighted entry, if it's a directory. A no-op otherwise.
+    fn navigate_into_highlighted(&mut self) -> InquireResult<()> {
+        if let Some(entry) = self.current_entries.get(self.highlighted) {
+            if entry.is_dir() {
+                let dir = entry.path.clone();
+                self.navigate_into(dir)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders the current page of entries and the staged count, with the preview pane
+    /// appended below when
+    /// [`PathSelect::preview`] is enabled. This is the call site that finally reads
+    /// [Self::preview_lines]: earlier commits computed them in [Self::refresh_preview] but
+    /// nothing drew them, since [Self::prompt] never rendered at all.
+    fn render<B: MultiSelectBackend>(&self, backend: &mut B) -> InquireResult<()> {
+        let (page_entries, page_highlighted) = self.current_page();
+
+        let mut rows: Vec<String> = page_entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let cursor = if index == page_highlighted { '>' } else { ' ' };
+                let marker = if self.flagged.is_flagged(entry) { "[x]" } else { "[ ]" };
+                format!("{cursor} {marker} {entry}")
+            })
+            .collect();
+
+        rows.push(format!("-- {} staged --", self.staged_count()));
+
+        if self.config.preview {
+            rows.push("-- preview --".to_string());
+            rows.extend(self.preview_lines.iter().cloned());
+        }
+
+        backend.render_multiselect_prompt(self.config.message, &rows)
+    }
+
+    /// Builds the final answer from the accumulated selection (or the single highlighted
+    /// entry, when [`PathSelect::select_multiple`] is disabled), draining [Self::flagged].
+    fn finalize(&mut self) -> InquireResult<Vec<ListOption<PathEntry>>> {
+        if self.config.select_multiple {
+            let selected_paths = std::mem::take(&mut self.flagged).into_paths();
+
+            selected_paths
+                .into_iter()
+                .enumerate()
+                .map(|(index, path)| {
+                    PathEntry::try_from(path.as_path()).map(|entry| ListOption::new(index, entry))
+                })
+                .collect()
+        } else {
+            Ok(self
+                .current_entries
+                .get(self.highlighted)
+                .cloned()
+                .into_iter()
+                .enumerate()
+                .map(|(index, entry)| ListOption::new(index, entry))
+                .collect())
+        }
+    }
+
+    /// Drives the interactive session: renders the current page and preview, reads a key from
+    /// `backend`, and dispatches it to navigation, bulk selection, or submission. Loops until
+    /// the user submits (`Enter`) or cancels (`Esc`).
+    pub(crate) fn prompt<B: MultiSelectBackend>(
+        mut self,
+        backend: &mut B,
+    ) -> InquireResult<Vec<ListOption<PathEntry>>> {
+        let answer = loop {
+            self.render(backend)?;
+
+            match backend.read_key()? {
+                Key::Up => self.move_highlight(-1),
+                Key::Down => self.move_highlight(1),
+                Key::Right => self.navigate_into_highlighted()?,
+                Key::Left => self.navigate_up()?,
+                Key::Char(' ') => self.toggle_highlighted(),
+                Key::Char('a') if self.config.select_multiple => {
+                    self.apply_action(PathSelectAction::FlagAll)
+                }
+                Key::Char('i') if self.config.select_multiple => {
+                    self.apply_action(PathSelectAction::InvertFlags)
+                }
+                Key::Char('c') if self.config.select_multiple => {
+                    self.apply_action(PathSelectAction::ClearFlags)
+                }
+                Key::Enter => break self.finalize()?,
+                Key::Escape => return Err(InquireError::OperationCanceled),
+                _ => {}
+            }
+        };
+
+        Ok(answer)
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use super::PathEntry;
+
+/// Accumulated multi-select state that survives directory navigation.
+///
+/// Populated by the bulk selection actions bound in the `action` module ("flag every
+/// selectable entry", "invert the current directory's flags", "clear all flags everywhere"),
+/// mirroring `fm`'s `flag_all`/`reverse_flags`/`clear_flags`. The prompt render path reads
+/// [FlaggedSelection::len] to show the staged count before submission.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FlaggedSelection {
+    flagged: HashSet<PathBuf>,
+}
+
+impl FlaggedSelection {
+    /// Number of paths currently flagged, across every directory visited so far.
+    pub(crate) fn len(&self) -> usize {
+        self.flagged.len()
+    }
+
+    /// Whether `entry` is currently flagged.
+    pub(crate) fn is_flagged(&self, entry: &PathEntry) -> bool {
+        self.flagged.contains(&entry.path)
+    }
+
+    /// Flags every entry in `current_dir_entries`.
+    pub(crate) fn flag_all(&mut self, current_dir_entries: &[PathEntry]) {
+        self.flagged
+            .extend(current_dir_entries.iter().map(|entry| entry.path.clone()));
+    }
+
+    /// Inverts the flagged state of every entry in `current_dir_entries`, leaving entries in
+    /// other directories untouched.
+    pub(crate) fn invert(&mut self, current_dir_entries: &[PathEntry]) {
+        for entry in current_dir_entries {
+            if !self.flagged.remove(&entry.path) {
+                self.flagged.insert(entry.path.clone());
+            }
+        }
+    }
+
+    /// Clears every flag, across all directories visited so far.
+    pub(crate) fn clear(&mut self) {
+        self.flagged.clear();
+    }
+
+    /// Consumes the accumulated selection, returning the flagged paths.
+    pub(crate) fn into_paths(self) -> Vec<PathBuf> {
+        self.flagged.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FlaggedSelection;
+    use crate::PathEntry;
+    use std::convert::TryFrom;
+    use std::path::Path;
+
+    fn entry(path: &str) -> PathEntry {
+        PathEntry::try_from(Path::new(path)).expect("test path should resolve")
+    }
+
+    #[test]
+    fn flag_all_then_clear() {
+        let mut selection = FlaggedSelection::default();
+        let entries = vec![entry("."), entry("..")];
+
+        selection.flag_all(&entries);
+        assert_eq!(selection.len(), 2);
+
+        selection.clear();
+        assert_eq!(selection.len(), 0);
+    }
+
+    #[test]
+    fn invert_toggles_only_current_directory_entries() {
+        let mut selection = FlaggedSelection::default();
+        let dir_a = vec![entry(".")];
+        let dir_b = vec![entry("..")];
+
+        selection.flag_all(&dir_b);
+        selection.invert(&dir_a);
+
+        assert!(selection.is_flagged(&dir_a[0]));
+        assert!(selection.is_flagged(&dir_b[0]));
+
+        selection.invert(&dir_a);
+        assert!(!selection.is_flagged(&dir_a[0]));
+        assert!(selection.is_flagged(&dir_b[0]));
+    }
+}
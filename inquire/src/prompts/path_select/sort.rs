@@ -0,0 +1,156 @@
+use std::cmp::Ordering;
+
+use super::PathEntry;
+
+/// Field used to order [`PathEntry`](super::PathEntry) results in
+/// [`PathSelect`](super::PathSelect), set via
+/// [`PathSelect::with_sort`](super::PathSelect::with_sort).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortField {
+    /// Sort by file name, using natural (version-aware) ordering.
+    Name,
+    /// Sort by file size in bytes.
+    Size,
+    /// Sort by last modified time.
+    Modified,
+    /// Sort by file extension, falling back to name for ties.
+    Extension,
+    /// Group directories before files, falling back to name for ties.
+    Type,
+}
+
+impl Default for SortField {
+    fn default() -> Self {
+        Self::Name
+    }
+}
+
+impl SortField {
+    fn cmp(&self, a: &PathEntry, b: &PathEntry) -> Ordering {
+        match self {
+            Self::Name => natural_cmp(&entry_name(a), &entry_name(b)),
+            Self::Size => entry_size(a)
+                .cmp(&entry_size(b))
+                .then_with(|| natural_cmp(&entry_name(a), &entry_name(b))),
+            Self::Modified => entry_modified(a)
+                .cmp(&entry_modified(b))
+                .then_with(|| natural_cmp(&entry_name(a), &entry_name(b))),
+            Self::Extension => entry_extension(a)
+                .cmp(&entry_extension(b))
+                .then_with(|| natural_cmp(&entry_name(a), &entry_name(b))),
+            Self::Type => b
+                .is_dir()
+                .cmp(&a.is_dir())
+                .then_with(|| natural_cmp(&entry_name(a), &entry_name(b))),
+        }
+    }
+}
+
+/// Sorts `entries` in place by `field`, reversing the ordering when `reversed` is set.
+///
+/// Applied after filtering and before pagination, mirroring where
+/// [`PathSelect::sort_field`](super::PathSelect::sort_field) is documented to take effect.
+pub(crate) fn sort_entries(entries: &mut [PathEntry], field: SortField, reversed: bool) {
+    entries.sort_by(|a, b| {
+        let ordering = field.cmp(a, b);
+        if reversed {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+fn entry_name(entry: &PathEntry) -> String {
+    entry
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn entry_size(entry: &PathEntry) -> u64 {
+    entry.path.metadata().map(|m| m.len()).unwrap_or_default()
+}
+
+fn entry_modified(entry: &PathEntry) -> std::time::SystemTime {
+    entry
+        .path
+        .metadata()
+        .and_then(|m| m.modified())
+        .unwrap_or(std::time::UNIX_EPOCH)
+}
+
+fn entry_extension(entry: &PathEntry) -> String {
+    entry
+        .path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Natural (version-aware) string comparison.
+///
+/// Splits each string into alternating runs of digits and non-digits and compares run-by-run:
+/// digit runs are compared numerically (so `file9` sorts before `file10`) and non-digit runs
+/// by case-insensitive byte order, with ties broken by the raw string. Mirrors the ordering
+/// `exa`'s `compare_files` produces for mixed letter/number file names.
+pub(crate) fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_runs = split_runs(a);
+    let mut b_runs = split_runs(b);
+
+    loop {
+        match (a_runs.next(), b_runs.next()) {
+            (Some(a_run), Some(b_run)) => {
+                let ordering = match (a_run.parse::<u128>(), b_run.parse::<u128>()) {
+                    (Ok(a_num), Ok(b_num)) => a_num.cmp(&b_num),
+                    _ => a_run.to_lowercase().cmp(&b_run.to_lowercase()),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (None, None) => break,
+        }
+    }
+
+    a.cmp(b)
+}
+
+fn split_runs(s: &str) -> impl Iterator<Item = &str> {
+    let mut chars = s.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, first_char) = chars.next()?;
+        let is_digit = first_char.is_ascii_digit();
+        let mut end = start + first_char.len_utf8();
+        while let Some(&(idx, c)) = chars.peek() {
+            if c.is_ascii_digit() != is_digit {
+                break;
+            }
+            end = idx + c.len_utf8();
+            chars.next();
+        }
+        Some(&s[start..end])
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::natural_cmp;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn natural_cmp_orders_digit_runs_numerically() {
+        assert_eq!(natural_cmp("file9", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file9"), Ordering::Greater);
+        assert_eq!(natural_cmp("file2", "file2"), Ordering::Equal);
+    }
+
+    #[test]
+    fn natural_cmp_is_case_insensitive_on_letters() {
+        assert_eq!(natural_cmp("Apple", "apple"), Ordering::Less);
+        assert_eq!(natural_cmp("banana", "Apple"), Ordering::Greater);
+    }
+}